@@ -1,17 +1,30 @@
 use nih_plug::editor::Editor;
 use nih_plug_vizia::vizia::prelude::*;
-use nih_plug_vizia::widgets::{ParamSlider, ParamSliderExt};
+use nih_plug_vizia::widgets::{ParamButton, ParamSlider, ParamSliderExt};
 use nih_plug_vizia::{create_vizia_editor, ViziaState, ViziaTheming};
+use std::path::PathBuf;
 use std::sync::Arc;
 
-use crate::HrtfConvParams;
+use crate::{HrtfConvParams, MAX_SOURCES};
 
 #[derive(Lens)]
 struct Data {
     params: Arc<HrtfConvParams>,
 }
 
-impl Model for Data {}
+enum SofaFileEvent {
+    Load(PathBuf),
+}
+
+impl Model for Data {
+    fn event(&mut self, _cx: &mut EventContext, event: &mut Event) {
+        event.map(|event, _| match event {
+            SofaFileEvent::Load(path) => {
+                *self.params.sofa_path.write().unwrap() = Some(path.clone());
+            }
+        });
+    }
+}
 
 pub(crate) fn default_state() -> Arc<ViziaState> {
     ViziaState::new(|| (400, 300))
@@ -28,18 +41,59 @@ pub(crate) fn create(
         .build(cx);
 
         VStack::new(cx, |cx| {
-            ParamSlider::new(cx, Data::params, |params| &params.azimuth)
-                .with_label("az")
-                .border_radius("10")
-                .size(Stretch(10.0));
-            ParamSlider::new(cx, Data::params, |params| &params.elevation)
-                .with_label("el")
-                .border_radius("10")
-                .size(Stretch(10.0));
-            ParamSlider::new(cx, Data::params, |params| &params.distance)
-                .with_label("distance")
-                .border_radius("10")
-                .size(Stretch(10.0));
+            for i in 0..MAX_SOURCES {
+                HStack::new(cx, move |cx| {
+                    Label::new(cx, &format!("Source {}", i + 1));
+                    ParamSlider::new(cx, Data::params, move |params| &params.sources[i].azimuth)
+                        .with_label("az")
+                        .border_radius("10")
+                        .size(Stretch(10.0));
+                    ParamSlider::new(cx, Data::params, move |params| &params.sources[i].elevation)
+                        .with_label("el")
+                        .border_radius("10")
+                        .size(Stretch(10.0));
+                    ParamSlider::new(cx, Data::params, move |params| &params.sources[i].distance)
+                        .with_label("distance")
+                        .border_radius("10")
+                        .size(Stretch(10.0));
+                    ParamButton::new(cx, Data::params, move |params| &params.sources[i].orbit);
+                    ParamSlider::new(cx, Data::params, move |params| {
+                        &params.sources[i].orbit_rate
+                    })
+                    .with_label("orbit rate")
+                    .border_radius("10")
+                    .size(Stretch(10.0));
+                    ParamSlider::new(cx, Data::params, move |params| {
+                        &params.sources[i].orbit_radius
+                    })
+                    .with_label("orbit radius")
+                    .border_radius("10")
+                    .size(Stretch(10.0));
+                    ParamSlider::new(cx, Data::params, move |params| {
+                        &params.sources[i].orbit_elevation
+                    })
+                    .with_label("orbit elevation")
+                    .border_radius("10")
+                    .size(Stretch(10.0));
+                    ParamButton::new(cx, Data::params, move |params| {
+                        &params.sources[i].distance_model
+                    });
+                });
+            }
+            ParamButton::new(cx, Data::params, |params| &params.interpolate_hrir);
+            Button::new(
+                cx,
+                |cx| {
+                    if let Some(path) = rfd::FileDialog::new()
+                        .add_filter("SOFA", &["sofa"])
+                        .pick_file()
+                    {
+                        cx.emit(SofaFileEvent::Load(path));
+                    }
+                },
+                |cx| Label::new(cx, "Load SOFA file..."),
+            )
+            .size(Stretch(10.0));
         });
         // ResizeHandle::new(cx);
     })
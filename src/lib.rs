@@ -4,33 +4,81 @@ use sofar::{
     reader::{Filter, OpenOptions, Sofar},
     render::Renderer,
 };
-use std::{io::Cursor, sync::Arc};
+use std::{
+    io::Cursor,
+    path::PathBuf,
+    sync::{Arc, Mutex, RwLock},
+};
 mod editor;
 
 const PARTITION_LEN: usize = 32;
 
+// Length of the crossfade applied when a source's direction changes, so
+// swapping HRIRs doesn't click. May span several blocks at small buffer
+// sizes.
+const CROSSFADE_MS: f32 = 20.0;
+
+// Nominal azimuth/elevation spacing (in degrees) used to locate the four
+// measured directions bracketing an arbitrary direction for interpolation.
+// Real SOFA databases are measured on a variety of grids; snapping to this
+// coarse, uniform grid for the bracketing corners still removes most of the
+// audible stepping compared to nearest-neighbor lookup.
+const INTERP_GRID_DEG: f32 = 5.0;
+
+// The elevation LFO used by orbit mode runs at this fraction of the azimuth
+// orbit rate, nodding by this many degrees around `orbit_elevation`.
+const ORBIT_ELEVATION_LFO_RATIO: f32 = 0.25;
+const ORBIT_ELEVATION_DEPTH_DEG: f32 = 15.0;
+
+// Distance model constants. `MIN_DISTANCE_M`/`MAX_DISTANCE_M` match the
+// `distance`/`orbit_radius` parameter ranges in `SourceParams::new` below.
+const SPEED_OF_SOUND_M_S: f32 = 343.0;
+const MIN_DISTANCE_M: f32 = 0.1;
+const MAX_DISTANCE_M: f32 = 1.0;
+// Air-absorption low-pass cutoff at the nearest and farthest distances.
+const MAX_AIR_ABSORPTION_CUTOFF_HZ: f32 = 20_000.0;
+const MIN_AIR_ABSORPTION_CUTOFF_HZ: f32 = 1_500.0;
+
+// The largest number of point sources any of `AUDIO_IO_LAYOUTS` asks for.
+// `HrtfConvParams::sources` is sized to this so every layout can be selected
+// without reallocating the parameter list.
+pub(crate) const MAX_SOURCES: usize = 8;
+
 static SOFA_DATA: &[u8] = include_bytes!("assets/mit_kemar_normal_pinna.sofa");
 
-// parameters and gui state
+// Per-source position parameters. One of these is instantiated per entry in
+// `HrtfConvParams::sources`.
 #[derive(Params)]
-struct HrtfConvParams {
-    #[persist = "editor-state"]
-    editor_state: Arc<ViziaState>,
+struct SourceParams {
     #[id = "azimuth"]
     pub azimuth: FloatParam,
     #[id = "elevation"]
     pub elevation: FloatParam,
     #[id = "distance"]
     pub distance: FloatParam,
+    // When enabled, `azimuth` (and, gently, `elevation`) are overridden by an
+    // LFO that continuously rotates the source around the listener instead
+    // of tracking automation.
+    #[id = "orbit"]
+    pub orbit: BoolParam,
+    #[id = "orbit-rate"]
+    pub orbit_rate: FloatParam,
+    #[id = "orbit-radius"]
+    pub orbit_radius: FloatParam,
+    #[id = "orbit-elevation"]
+    pub orbit_elevation: FloatParam,
+    // When enabled, applies inverse-distance gain, propagation delay and an
+    // air-absorption low-pass on top of the HRTF convolution, instead of
+    // relying on `distance` only re-selecting a nearer/farther HRIR.
+    #[id = "distance-model"]
+    pub distance_model: BoolParam,
 }
 
-impl Default for HrtfConvParams {
-    fn default() -> Self {
+impl SourceParams {
+    fn new(index: usize) -> Self {
         Self {
-            editor_state: editor::default_state(),
-
             azimuth: FloatParam::new(
-                "Azimuth",
+                format!("Source {} Azimuth", index + 1),
                 0.0,
                 FloatRange::Linear {
                     min: 0.0,
@@ -41,7 +89,7 @@ impl Default for HrtfConvParams {
             .with_smoother(SmoothingStyle::Logarithmic(50.0))
             .with_step_size(0.01),
             elevation: FloatParam::new(
-                "Elevation",
+                format!("Source {} Elevation", index + 1),
                 0.0,
                 FloatRange::Linear {
                     min: 0.0,
@@ -51,22 +99,236 @@ impl Default for HrtfConvParams {
             .with_unit("°")
             .with_smoother(SmoothingStyle::Logarithmic(50.0))
             .with_step_size(0.01),
-            distance: FloatParam::new("Distance", 1.0, FloatRange::Linear { min: 0.1, max: 1.0 })
-                .with_unit("m")
-                .with_smoother(SmoothingStyle::Logarithmic(50.0))
-                .with_step_size(0.05),
+            distance: FloatParam::new(
+                format!("Source {} Distance", index + 1),
+                1.0,
+                FloatRange::Linear { min: 0.1, max: 1.0 },
+            )
+            .with_unit("m")
+            .with_smoother(SmoothingStyle::Logarithmic(50.0))
+            .with_step_size(0.05),
+            orbit: BoolParam::new(format!("Source {} Orbit", index + 1), false),
+            orbit_rate: FloatParam::new(
+                format!("Source {} Orbit Rate", index + 1),
+                0.1,
+                FloatRange::Skewed {
+                    min: 0.01,
+                    max: 2.0,
+                    factor: FloatRange::skew_factor(-1.0),
+                },
+            )
+            .with_unit(" Hz")
+            .with_step_size(0.01),
+            orbit_radius: FloatParam::new(
+                format!("Source {} Orbit Radius", index + 1),
+                1.0,
+                FloatRange::Linear { min: 0.1, max: 1.0 },
+            )
+            .with_unit("m")
+            .with_step_size(0.05),
+            orbit_elevation: FloatParam::new(
+                format!("Source {} Orbit Elevation", index + 1),
+                90.0,
+                FloatRange::Linear {
+                    min: 0.0,
+                    max: 180.0,
+                },
+            )
+            .with_unit("°")
+            .with_step_size(0.01),
+            distance_model: BoolParam::new(format!("Source {} Distance Model", index + 1), false),
+        }
+    }
+
+    /// Returns the source's direction as `(azimuth_deg, elevation_deg, distance)`.
+    fn spherical(&self) -> (f32, f32, f32) {
+        (
+            self.azimuth.value(),
+            self.elevation.value(),
+            self.distance.value(),
+        )
+    }
+}
+
+/// Converts a (azimuth_deg, elevation_deg, distance) triple to the Cartesian
+/// coordinates `Sofar::filter` expects.
+fn to_cartesian(azimuth_deg: f32, elevation_deg: f32, distance: f32) -> (f32, f32, f32) {
+    let az = azimuth_deg.to_radians();
+    let el = elevation_deg.to_radians();
+    (
+        distance * (el.cos() * az.cos()),
+        distance * (el.cos() * az.sin()),
+        distance * el.sin(),
+    )
+}
+
+// parameters and gui state
+#[derive(Params)]
+struct HrtfConvParams {
+    #[persist = "editor-state"]
+    editor_state: Arc<ViziaState>,
+    // Path to a user-supplied SOFA HRIR database. `None` means "use the
+    // embedded MIT KEMAR set".
+    #[persist = "sofa-path"]
+    sofa_path: Arc<RwLock<Option<PathBuf>>>,
+    // When enabled, HRIRs are synthesized by interpolating the nearest
+    // measured directions instead of snapping to whichever one the SOFA
+    // reader picks for the requested direction.
+    #[id = "interpolate-hrir"]
+    pub interpolate_hrir: BoolParam,
+    // One independent point source per main input channel. Only the first
+    // `num_sources` (set by the negotiated `AudioIOLayout`) are actually
+    // rendered.
+    #[nested(array, group = "sources")]
+    sources: [SourceParams; MAX_SOURCES],
+}
+
+impl Default for HrtfConvParams {
+    fn default() -> Self {
+        Self {
+            editor_state: editor::default_state(),
+            sofa_path: Arc::new(RwLock::new(None)),
+            interpolate_hrir: BoolParam::new("Interpolate HRIR", true),
+            sources: std::array::from_fn(SourceParams::new),
+        }
+    }
+}
+
+// A small circular buffer providing a linearly-interpolated fractional
+// delay, used to model propagation delay in the distance cue model.
+struct DistanceDelayLine {
+    buffer: Vec<f32>,
+    write_pos: usize,
+}
+
+impl DistanceDelayLine {
+    fn new(capacity_samples: usize) -> Self {
+        Self {
+            buffer: vec![0.0; capacity_samples.max(2)],
+            write_pos: 0,
         }
     }
+
+    fn clear(&mut self) {
+        for sample in &mut self.buffer {
+            *sample = 0.0;
+        }
+        self.write_pos = 0;
+    }
+
+    // Writes `input` and returns the signal delayed by `delay_samples`,
+    // linearly interpolated between the two nearest integer delays. The
+    // caller must keep `delay_samples` below `self.buffer.len() - 1`.
+    fn process(&mut self, input: f32, delay_samples: f32) -> f32 {
+        let len = self.buffer.len();
+        self.buffer[self.write_pos] = input;
+
+        let delay_samples = delay_samples.clamp(0.0, (len - 1) as f32);
+        let delay_floor = delay_samples.floor();
+        let frac = delay_samples - delay_floor;
+
+        let read_pos_a = (self.write_pos + len - delay_floor as usize) % len;
+        let read_pos_b = (read_pos_a + len - 1) % len;
+        let out = self.buffer[read_pos_a] * (1.0 - frac) + self.buffer[read_pos_b] * frac;
+
+        self.write_pos = (self.write_pos + 1) % len;
+        out
+    }
+}
+
+// An in-progress linear crossfade from one of a source's two filter slots to
+// the other, driven sample-by-sample as blocks are rendered.
+struct CrossfadeState {
+    // The slot (0 or 1) that is fading in. The other slot is fading out.
+    incoming_slot: usize,
+    samples_done: usize,
+    ramp_len: usize,
+}
+
+impl CrossfadeState {
+    /// Linear fade gains `(gain_old, gain_new)` for the `n`-th sample of the
+    /// current block, based on how far into the ramp this crossfade is.
+    fn gains_at(&self, n: usize) -> (f32, f32) {
+        let progress = ((self.samples_done + n) as f32 / self.ramp_len as f32).min(1.0);
+        (1.0 - progress, progress)
+    }
+}
+
+// Dispatched to a background task when the user picks a new SOFA file, so
+// the disk read, SOFA parsing, and FFT planning run off the audio thread.
+struct SofaReloadTask {
+    path: Option<PathBuf>,
+    sample_rate: f32,
+    directions: Vec<(f32, f32, f32)>,
+    interpolate: bool,
+}
+
+// The result of a `SofaReloadTask`, built entirely off the audio thread.
+// `process` swaps this into `HrtfConv` a field at a time once it's ready.
+struct PendingSofaBuild {
+    sofa: Sofar,
+    filters: [Vec<Filter>; 2],
+    renderers: [Vec<Renderer>; 2],
+    directions: Vec<(f32, f32, f32)>,
+    path: Option<PathBuf>,
+    corner_filter_scratch: [Filter; 4],
+    corner_shift_left: [Vec<f32>; 4],
+    corner_shift_right: [Vec<f32>; 4],
 }
 
 // plugin struct
 struct HrtfConv {
     params: Arc<HrtfConvParams>,
     sofa: Option<Sofar>,
-    filter: Option<Filter>,
-    renderer: Option<Renderer>,
+    // Two filter/renderer slots per source, so a direction change can
+    // crossfade from the old slot to the new one instead of swapping the
+    // filter under a single renderer mid-stream.
+    filters: [Vec<Filter>; 2],
+    renderers: [Vec<Renderer>; 2],
+    active_slot: Vec<usize>,
+    crossfade: Vec<Option<CrossfadeState>>,
+    last_directions: Vec<(f32, f32, f32)>,
+    num_sources: usize,
+    crossfade_len_samples: usize,
+    // Scratch space, reused across sources so `process` stays allocation-free.
     scratch_buffer: Vec<f32>,
-    last_direction: (f32, f32, f32),
+    left_scratch: Vec<f32>,
+    right_scratch: Vec<f32>,
+    left_scratch_incoming: Vec<f32>,
+    right_scratch_incoming: Vec<f32>,
+    // Each source's fully-mixed (post-crossfade) render, before the optional
+    // distance model and summation into `accum_left`/`accum_right`.
+    mixed_left: Vec<f32>,
+    mixed_right: Vec<f32>,
+    accum_left: Vec<f32>,
+    accum_right: Vec<f32>,
+    // Per-source propagation-delay lines and air-absorption low-pass state
+    // for the optional distance model. Only touched when a source has
+    // `distance_model` enabled.
+    distance_delay_left: Vec<DistanceDelayLine>,
+    distance_delay_right: Vec<DistanceDelayLine>,
+    air_absorption_left: Vec<f32>,
+    air_absorption_right: Vec<f32>,
+    // The SOFA path that `sofa`/`filters`/`renderers` were last built from, so
+    // `process` can notice when the user picks a new file and rebuild.
+    loaded_sofa_path: Option<PathBuf>,
+    sample_rate: f32,
+    // Per-source orbit LFO phase accumulators, in degrees. `orbit_phase`
+    // drives azimuth directly; `orbit_elevation_phase` drives the slower
+    // elevation nod.
+    orbit_phase: Vec<f32>,
+    orbit_elevation_phase: Vec<f32>,
+    // Scratch space for `interpolated_filter`'s up-to-four bracketing
+    // corners, reused across direction changes so re-filtering on the audio
+    // thread doesn't allocate. Sized to `filter_len` in `rebuild`.
+    corner_filter_scratch: [Filter; 4],
+    corner_shift_left: [Vec<f32>; 4],
+    corner_shift_right: [Vec<f32>; 4],
+    // Set while a `SofaReloadTask` is in flight, so `process` doesn't
+    // dispatch a second one before the first finishes. The task writes its
+    // outcome into `pending_sofa_build`, which `process` drains each block.
+    sofa_reload_in_flight: bool,
+    pending_sofa_build: Arc<Mutex<Option<Result<PendingSofaBuild, ()>>>>,
 }
 
 impl Default for HrtfConv {
@@ -75,10 +337,438 @@ impl Default for HrtfConv {
         Self {
             params: Arc::new(HrtfConvParams::default()),
             sofa: None,
-            filter: None,
-            renderer: None,
+            filters: [vec![], vec![]],
+            renderers: [vec![], vec![]],
+            active_slot: vec![],
+            crossfade: vec![],
+            last_directions: vec![],
+            num_sources: 1,
+            crossfade_len_samples: 1,
             scratch_buffer: vec![],
-            last_direction: (0.0, 0.0, 0.0),
+            left_scratch: vec![],
+            right_scratch: vec![],
+            left_scratch_incoming: vec![],
+            right_scratch_incoming: vec![],
+            mixed_left: vec![],
+            mixed_right: vec![],
+            accum_left: vec![],
+            accum_right: vec![],
+            distance_delay_left: vec![],
+            distance_delay_right: vec![],
+            air_absorption_left: vec![],
+            air_absorption_right: vec![],
+            loaded_sofa_path: None,
+            sample_rate: 1.0,
+            orbit_phase: vec![],
+            orbit_elevation_phase: vec![],
+            corner_filter_scratch: std::array::from_fn(|_| Filter::new(1)),
+            corner_shift_left: std::array::from_fn(|_| vec![]),
+            corner_shift_right: std::array::from_fn(|_| vec![]),
+            sofa_reload_in_flight: false,
+            pending_sofa_build: Arc::new(Mutex::new(None)),
+        }
+    }
+}
+
+impl HrtfConv {
+    /// Opens the SOFA database at `path`, falling back to the embedded MIT
+    /// KEMAR set if `path` is `None` or fails to parse.
+    fn open_sofa(path: Option<&PathBuf>, sample_rate: f32) -> Sofar {
+        if let Some(path) = path {
+            match OpenOptions::new().sample_rate(sample_rate).open(path) {
+                Ok(sofa) => return sofa,
+                Err(e) => nih_error!(
+                    "Failed to load SOFA file {}: {e}, falling back to the built-in HRIR set",
+                    path.display()
+                ),
+            }
+        }
+
+        let cursor = Cursor::new(SOFA_DATA);
+        OpenOptions::new()
+            .sample_rate(sample_rate)
+            .open_data(cursor.get_ref())
+            .expect("Failed to open embedded HRTF data")
+    }
+
+    /// Computes the filter for `direction` (azimuth_deg, elevation_deg,
+    /// distance), either by asking the SOFA reader for its nearest measured
+    /// direction, or, when `interpolate` is set, by blending the four
+    /// bracketing measured directions. Writes the result into `dst`, reusing
+    /// its existing buffers; `corner_scratch` and the `shift_scratch_*` pair
+    /// back the interpolated path's up-to-four bracketing corners so this
+    /// never allocates once `rebuild` has sized them.
+    fn compute_filter(
+        sofa: &Sofar,
+        filter_len: usize,
+        direction: (f32, f32, f32),
+        interpolate: bool,
+        dst: &mut Filter,
+        corner_scratch: &mut [Filter; 4],
+        shift_scratch_left: &mut [Vec<f32>; 4],
+        shift_scratch_right: &mut [Vec<f32>; 4],
+    ) {
+        if interpolate {
+            Self::interpolated_filter(
+                sofa,
+                filter_len,
+                direction,
+                dst,
+                corner_scratch,
+                shift_scratch_left,
+                shift_scratch_right,
+            );
+            return;
+        }
+
+        let (azimuth_deg, elevation_deg, distance) = direction;
+        let (x, y, z) = to_cartesian(azimuth_deg, elevation_deg, distance);
+        sofa.filter(x, y, z, dst);
+    }
+
+    /// Locates the four measured directions on the nominal `INTERP_GRID_DEG`
+    /// grid that bracket `(azimuth_deg, elevation_deg)`, returning each as
+    /// `(corner_azimuth_deg, corner_elevation_deg, bilinear_weight)`. Weights
+    /// sum to 1.0; when the direction lies exactly on a grid line, the
+    /// corners on the far side of that line get weight 0.0.
+    fn bilinear_corners(azimuth_deg: f32, elevation_deg: f32) -> [(f32, f32, f32); 4] {
+        let az_lo = (azimuth_deg / INTERP_GRID_DEG).floor() * INTERP_GRID_DEG;
+        let el_lo = (elevation_deg / INTERP_GRID_DEG).floor() * INTERP_GRID_DEG;
+        let az_t = (azimuth_deg - az_lo) / INTERP_GRID_DEG;
+        let el_t = (elevation_deg - el_lo) / INTERP_GRID_DEG;
+
+        [
+            (az_lo, el_lo, (1.0 - az_t) * (1.0 - el_t)),
+            (az_lo + INTERP_GRID_DEG, el_lo, az_t * (1.0 - el_t)),
+            (az_lo, el_lo + INTERP_GRID_DEG, (1.0 - az_t) * el_t),
+            (
+                az_lo + INTERP_GRID_DEG,
+                el_lo + INTERP_GRID_DEG,
+                az_t * el_t,
+            ),
+        ]
+    }
+
+    /// Synthesizes an interpolated HRIR for `direction` by locating the four
+    /// measured directions on the nominal `INTERP_GRID_DEG` grid that bracket
+    /// it, fetching each corner's filter from `sofa`, and blending them with
+    /// bilinear weights. Each ear's onset (its peak-magnitude sample) is
+    /// interpolated separately from the raw taps and re-inserted afterwards,
+    /// so the blend doesn't smear the ITD the way averaging the raw taps
+    /// naively would. Writes the blended result into `dst` in place.
+    fn interpolated_filter(
+        sofa: &Sofar,
+        filter_len: usize,
+        direction: (f32, f32, f32),
+        dst: &mut Filter,
+        corner_scratch: &mut [Filter; 4],
+        shift_scratch_left: &mut [Vec<f32>; 4],
+        shift_scratch_right: &mut [Vec<f32>; 4],
+    ) {
+        let (azimuth_deg, elevation_deg, distance) = direction;
+        let corners = Self::bilinear_corners(azimuth_deg, elevation_deg);
+
+        // Fetch each non-zero-weight corner's filter into the matching
+        // scratch slot. `weights[i] > 0.0` marks that `corner_scratch[i]`
+        // holds this call's data.
+        let mut weights = [0.0f32; 4];
+        for (i, &(az_deg, el_deg, weight)) in corners.iter().enumerate() {
+            if weight <= 0.0 {
+                continue;
+            }
+            let (x, y, z) = to_cartesian(az_deg, el_deg, distance);
+            sofa.filter(x, y, z, &mut corner_scratch[i]);
+            weights[i] = weight;
+        }
+
+        let total_weight: f32 = weights.iter().sum();
+        let mut left_onsets = [0.0f32; 4];
+        let mut right_onsets = [0.0f32; 4];
+        for i in 0..4 {
+            if weights[i] <= 0.0 {
+                continue;
+            }
+            left_onsets[i] = Self::onset_index(&corner_scratch[i].left) as f32;
+            right_onsets[i] = Self::onset_index(&corner_scratch[i].right) as f32;
+        }
+
+        let target_left_onset: f32 =
+            (0..4).map(|i| left_onsets[i] * weights[i]).sum::<f32>() / total_weight;
+        let target_right_onset: f32 =
+            (0..4).map(|i| right_onsets[i] * weights[i]).sum::<f32>() / total_weight;
+
+        for sample in &mut dst.left {
+            *sample = 0.0;
+        }
+        for sample in &mut dst.right {
+            *sample = 0.0;
+        }
+
+        for i in 0..4 {
+            if weights[i] <= 0.0 {
+                continue;
+            }
+            let shift_left = (target_left_onset - left_onsets[i]).round() as isize;
+            let shift_right = (target_right_onset - right_onsets[i]).round() as isize;
+            Self::shift_into(
+                &corner_scratch[i].left,
+                shift_left,
+                &mut shift_scratch_left[i],
+            );
+            Self::shift_into(
+                &corner_scratch[i].right,
+                shift_right,
+                &mut shift_scratch_right[i],
+            );
+
+            for n in 0..filter_len {
+                dst.left[n] += weights[i] * shift_scratch_left[i][n];
+                dst.right[n] += weights[i] * shift_scratch_right[i][n];
+            }
+        }
+    }
+
+    /// Index of the sample with the largest magnitude, used as a cheap onset
+    /// estimate for ITD-preserving interpolation.
+    fn onset_index(ir: &[f32]) -> usize {
+        ir.iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.abs().partial_cmp(&b.abs()).unwrap())
+            .map(|(i, _)| i)
+            .unwrap_or(0)
+    }
+
+    /// Writes `ir` shifted right by `shift` samples (left if negative) into
+    /// `dst`, zero-filling the exposed edge.
+    fn shift_into(ir: &[f32], shift: isize, dst: &mut [f32]) {
+        let len = ir.len();
+        for (i, sample) in dst.iter_mut().enumerate() {
+            let src = i as isize - shift;
+            *sample = if src >= 0 && (src as usize) < len {
+                ir[src as usize]
+            } else {
+                0.0
+            };
+        }
+    }
+
+    /// Builds a single filter/renderer pair for `direction` against `sofa`.
+    fn build_voice(
+        sofa: &Sofar,
+        sample_rate: f32,
+        direction: (f32, f32, f32),
+        interpolate: bool,
+        corner_scratch: &mut [Filter; 4],
+        shift_scratch_left: &mut [Vec<f32>; 4],
+        shift_scratch_right: &mut [Vec<f32>; 4],
+    ) -> Option<(Filter, Renderer)> {
+        let filter_len = sofa.filter_len();
+        let mut filter = Filter::new(filter_len);
+        Self::compute_filter(
+            sofa,
+            filter_len,
+            direction,
+            interpolate,
+            &mut filter,
+            corner_scratch,
+            shift_scratch_left,
+            shift_scratch_right,
+        );
+
+        let mut render = match Renderer::builder(filter_len)
+            .with_sample_rate(sample_rate)
+            .with_partition_len(PARTITION_LEN)
+            .build()
+        {
+            Ok(render) => render,
+            Err(e) => {
+                nih_error!("Failed to create HRTF renderer: {e}");
+                return None;
+            }
+        };
+
+        if let Err(e) = render.set_filter(&filter) {
+            nih_error!("Failed to set filter: {e}");
+            return None;
+        }
+
+        Some((filter, render))
+    }
+
+    /// Opens `path` (or falls back to the embedded HRIR set) and builds both
+    /// filter/renderer slots for every entry in `directions`. This is the
+    /// expensive part of a rebuild (disk IO, SOFA parsing, FFT planning for
+    /// every renderer), and owns all of its own scratch state, so it's safe
+    /// to run off the audio thread from `task_executor`.
+    fn build_sofa(
+        path: Option<PathBuf>,
+        sample_rate: f32,
+        directions: Vec<(f32, f32, f32)>,
+        interpolate: bool,
+    ) -> Option<PendingSofaBuild> {
+        let sofa = Self::open_sofa(path.as_ref(), sample_rate);
+        let filter_len = sofa.filter_len();
+
+        let mut corner_filter_scratch: [Filter; 4] =
+            std::array::from_fn(|_| Filter::new(filter_len));
+        let mut corner_shift_left: [Vec<f32>; 4] = std::array::from_fn(|_| vec![0.0; filter_len]);
+        let mut corner_shift_right: [Vec<f32>; 4] = std::array::from_fn(|_| vec![0.0; filter_len]);
+
+        let mut slot_0 = Vec::with_capacity(directions.len());
+        let mut slot_1 = Vec::with_capacity(directions.len());
+        let mut filters_0 = Vec::with_capacity(directions.len());
+        let mut filters_1 = Vec::with_capacity(directions.len());
+
+        for &direction in &directions {
+            let (filter, render) = Self::build_voice(
+                &sofa,
+                sample_rate,
+                direction,
+                interpolate,
+                &mut corner_filter_scratch,
+                &mut corner_shift_left,
+                &mut corner_shift_right,
+            )?;
+            let (filter_alt, render_alt) = Self::build_voice(
+                &sofa,
+                sample_rate,
+                direction,
+                interpolate,
+                &mut corner_filter_scratch,
+                &mut corner_shift_left,
+                &mut corner_shift_right,
+            )?;
+
+            filters_0.push(filter);
+            slot_0.push(render);
+            filters_1.push(filter_alt);
+            slot_1.push(render_alt);
+        }
+
+        Some(PendingSofaBuild {
+            sofa,
+            filters: [filters_0, filters_1],
+            renderers: [slot_0, slot_1],
+            directions,
+            path,
+            corner_filter_scratch,
+            corner_shift_left,
+            corner_shift_right,
+        })
+    }
+
+    /// Swaps a finished `PendingSofaBuild` into place, starting each source
+    /// on slot 0 with no crossfade in progress.
+    fn apply_built_sofa(&mut self, built: PendingSofaBuild) {
+        let PendingSofaBuild {
+            sofa,
+            filters,
+            renderers,
+            directions,
+            path,
+            corner_filter_scratch,
+            corner_shift_left,
+            corner_shift_right,
+        } = built;
+
+        self.sofa = Some(sofa);
+        self.filters = filters;
+        self.renderers = renderers;
+        self.active_slot = vec![0; directions.len()];
+        self.crossfade = (0..directions.len()).map(|_| None).collect();
+        self.loaded_sofa_path = path;
+        self.crossfade_len_samples = ((CROSSFADE_MS / 1000.0) * self.sample_rate) as usize;
+        self.crossfade_len_samples = self.crossfade_len_samples.max(1);
+
+        self.orbit_phase.resize(directions.len(), 0.0);
+        self.orbit_elevation_phase.resize(directions.len(), 0.0);
+
+        self.corner_filter_scratch = corner_filter_scratch;
+        self.corner_shift_left = corner_shift_left;
+        self.corner_shift_right = corner_shift_right;
+
+        self.last_directions = directions;
+    }
+
+    /// Builds `sofa` and both filter/renderer slots synchronously. Only used
+    /// from `initialize`, where blocking is fine; mid-session reloads go
+    /// through `SofaReloadTask`/`task_executor` instead so `process` never
+    /// blocks on disk IO.
+    fn rebuild(&mut self, path: Option<PathBuf>, directions: &[(f32, f32, f32)]) -> bool {
+        let interpolate = self.params.interpolate_hrir.value();
+        let Some(built) =
+            Self::build_sofa(path, self.sample_rate, directions.to_vec(), interpolate)
+        else {
+            return false;
+        };
+        self.apply_built_sofa(built);
+        true
+    }
+
+    /// Each active source's direction as `(azimuth_deg, elevation_deg, distance)`.
+    /// Sources with orbit mode enabled get their azimuth (and, gently, their
+    /// elevation) from the LFO phase accumulators instead of from automation.
+    fn source_directions(&self) -> Vec<(f32, f32, f32)> {
+        self.params.sources[..self.num_sources]
+            .iter()
+            .enumerate()
+            .map(|(i, source)| {
+                if source.orbit.value() {
+                    let elevation = source.orbit_elevation.value()
+                        + ORBIT_ELEVATION_DEPTH_DEG
+                            * self.orbit_elevation_phase[i].to_radians().sin();
+                    (self.orbit_phase[i], elevation, source.orbit_radius.value())
+                } else {
+                    source.spherical()
+                }
+            })
+            .collect()
+    }
+
+    /// Advances each orbiting source's LFO phase accumulators by one block.
+    fn advance_orbit_phases(&mut self, num_samples: usize) {
+        for i in 0..self.num_sources {
+            if !self.params.sources[i].orbit.value() {
+                continue;
+            }
+
+            let rate = self.params.sources[i].orbit_rate.value();
+            let delta_deg = rate * num_samples as f32 / self.sample_rate * 360.0;
+            self.orbit_phase[i] = (self.orbit_phase[i] + delta_deg).rem_euclid(360.0);
+            self.orbit_elevation_phase[i] = (self.orbit_elevation_phase[i]
+                + delta_deg * ORBIT_ELEVATION_LFO_RATIO)
+                .rem_euclid(360.0);
+        }
+    }
+
+    // Applies inverse-distance gain, propagation delay and an air-absorption
+    // low-pass to source `i`'s mixed render, in place. `distance` is in the
+    // same units as the `distance` parameter (meters).
+    fn apply_distance_model(&mut self, i: usize, distance: f32, num_samples: usize) {
+        let distance = distance.max(MIN_DISTANCE_M);
+        let gain = 1.0 / distance;
+        let delay_samples = distance / SPEED_OF_SOUND_M_S * self.sample_rate;
+
+        let normalized =
+            ((distance - MIN_DISTANCE_M) / (MAX_DISTANCE_M - MIN_DISTANCE_M)).clamp(0.0, 1.0);
+        let cutoff_hz = MAX_AIR_ABSORPTION_CUTOFF_HZ
+            + normalized * (MIN_AIR_ABSORPTION_CUTOFF_HZ - MAX_AIR_ABSORPTION_CUTOFF_HZ);
+        // One-pole lowpass coefficient for the given cutoff.
+        let rc = 1.0 / (2.0 * std::f32::consts::PI * cutoff_hz);
+        let dt = 1.0 / self.sample_rate;
+        let alpha = dt / (rc + dt);
+
+        for n in 0..num_samples {
+            let delayed_left =
+                self.distance_delay_left[i].process(self.mixed_left[n], delay_samples);
+            let delayed_right =
+                self.distance_delay_right[i].process(self.mixed_right[n], delay_samples);
+
+            self.air_absorption_left[i] += alpha * (delayed_left - self.air_absorption_left[i]);
+            self.air_absorption_right[i] += alpha * (delayed_right - self.air_absorption_right[i]);
+
+            self.mixed_left[n] = gain * self.air_absorption_left[i];
+            self.mixed_right[n] = gain * self.air_absorption_right[i];
         }
     }
 }
@@ -93,18 +783,51 @@ impl Plugin for HrtfConv {
 
     // The first audio IO layout is used as the default. The other layouts may be selected either
     // explicitly or automatically by the host or the user depending on the plugin API/backend.
-    const AUDIO_IO_LAYOUTS: &'static [AudioIOLayout] = &[AudioIOLayout {
-        main_input_channels: NonZeroU32::new(1),
-        main_output_channels: NonZeroU32::new(2),
+    // Each layout treats every main input channel as an independent point
+    // source, individually HRTF-convolved and mixed down to the stereo
+    // output.
+    const AUDIO_IO_LAYOUTS: &'static [AudioIOLayout] = &[
+        AudioIOLayout {
+            main_input_channels: NonZeroU32::new(1),
+            main_output_channels: NonZeroU32::new(2),
+
+            aux_input_ports: &[],
+            aux_output_ports: &[],
 
-        aux_input_ports: &[],
-        aux_output_ports: &[],
+            // Individual ports and the layout as a whole can be named here. By default these names
+            // are generated as needed. This layout will be called 'Stereo', while a layout with
+            // only one input and output channel would be called 'Mono'.
+            names: PortNames::const_default(),
+        },
+        AudioIOLayout {
+            main_input_channels: NonZeroU32::new(4),
+            main_output_channels: NonZeroU32::new(2),
 
-        // Individual ports and the layout as a whole can be named here. By default these names
-        // are generated as needed. This layout will be called 'Stereo', while a layout with
-        // only one input and output channel would be called 'Mono'.
-        names: PortNames::const_default(),
-    }];
+            aux_input_ports: &[],
+            aux_output_ports: &[],
+
+            names: PortNames {
+                layout: Some("4 Sources"),
+                main_input: Some("Sources"),
+                main_output: Some("Stereo"),
+                ..PortNames::const_default()
+            },
+        },
+        AudioIOLayout {
+            main_input_channels: NonZeroU32::new(8),
+            main_output_channels: NonZeroU32::new(2),
+
+            aux_input_ports: &[],
+            aux_output_ports: &[],
+
+            names: PortNames {
+                layout: Some("8 Sources"),
+                main_input: Some("Sources"),
+                main_output: Some("Stereo"),
+                ..PortNames::const_default()
+            },
+        },
+    ];
 
     const MIDI_INPUT: MidiConfig = MidiConfig::None;
     const MIDI_OUTPUT: MidiConfig = MidiConfig::None;
@@ -115,10 +838,11 @@ impl Plugin for HrtfConv {
     // messages here. The type implements the `SysExMessage` trait, which allows conversion to and
     // from plain byte buffers.
     type SysExMessage = ();
-    // More advanced plugins can use this to run expensive background tasks. See the field's
-    // documentation for more information. `()` means that the plugin does not have any background
-    // tasks.
-    type BackgroundTask = ();
+    // Reloading a user-supplied SOFA file involves blocking disk IO, SOFA
+    // parsing, and FFT planning for every renderer: too slow to do inline in
+    // `process`, so it's dispatched here and the result is swapped in once
+    // `task_executor` has finished building it.
+    type BackgroundTask = SofaReloadTask;
 
     fn params(&self) -> Arc<dyn Params> {
         self.params.clone()
@@ -128,57 +852,79 @@ impl Plugin for HrtfConv {
         editor::create(self.params.clone(), self.params.editor_state.clone())
     }
 
+    fn task_executor(&mut self) -> TaskExecutor<Self> {
+        let pending_sofa_build = self.pending_sofa_build.clone();
+        Box::new(move |task| {
+            let result = Self::build_sofa(
+                task.path,
+                task.sample_rate,
+                task.directions,
+                task.interpolate,
+            )
+            .ok_or(());
+            *pending_sofa_build.lock().unwrap() = Some(result);
+        })
+    }
+
     fn initialize(
         &mut self,
-        _audio_io_layout: &AudioIOLayout,
+        audio_io_layout: &AudioIOLayout,
         buffer_config: &BufferConfig,
         _context: &mut impl InitContext<Self>,
     ) -> bool {
-        let cursor = Cursor::new(SOFA_DATA);
-        let sofa = OpenOptions::new()
-            .sample_rate(buffer_config.sample_rate)
-            .open_data(cursor.get_ref());
-        if sofa.is_err() {
-            nih_error!("Failed to open HRTF data");
-            return false;
-        }
-        let sofa = sofa.unwrap();
-
-        let filter_len = sofa.filter_len();
+        self.sample_rate = buffer_config.sample_rate;
+        self.num_sources = audio_io_layout
+            .main_input_channels
+            .map_or(1, |n| n.get() as usize)
+            .min(MAX_SOURCES);
 
-        let az_deg = self.params.azimuth.value();
-        let el_deg = self.params.elevation.value();
-        let dist = self.params.distance.value();
-        let az = az_deg.to_radians();
-        let el = el_deg.to_radians();
-        let x = dist * (el.cos() * az.cos());
-        let y = dist * (el.cos() * az.sin());
-        let z = dist * el.sin();
-        let current_direction = (x, y, z);
+        // `source_directions` indexes these by source, so they must be sized
+        // before it's called (and before `rebuild`, which also relies on
+        // `num_sources` already being current).
+        self.orbit_phase.resize(self.num_sources, 0.0);
+        self.orbit_elevation_phase.resize(self.num_sources, 0.0);
 
-        let mut filter = Filter::new(filter_len);
-        sofa.filter(x, y, z, &mut filter);
+        let directions = self.source_directions();
 
-        let render = Renderer::builder(filter_len)
-            .with_sample_rate(buffer_config.sample_rate)
-            .with_partition_len(PARTITION_LEN)
-            .build();
-        if render.is_err() {
-            nih_error!("Failed to create HRTF renderer");
+        let path = self.params.sofa_path.read().unwrap().clone();
+        if !self.rebuild(path, &directions) {
             return false;
         }
-        let mut render = render.unwrap();
-
-        render.set_filter(&filter).expect("Failed to set filter");
-
-        self.sofa = Some(sofa);
-        self.filter = Some(filter);
-        self.renderer = Some(render);
-        self.last_direction = current_direction;
 
+        let max_buffer_size = buffer_config.max_buffer_size as usize;
         self.scratch_buffer.clear();
-        self.scratch_buffer
-            .resize(buffer_config.max_buffer_size as usize, 0.0);
+        self.scratch_buffer.resize(max_buffer_size, 0.0);
+        self.left_scratch.clear();
+        self.left_scratch.resize(max_buffer_size, 0.0);
+        self.right_scratch.clear();
+        self.right_scratch.resize(max_buffer_size, 0.0);
+        self.left_scratch_incoming.clear();
+        self.left_scratch_incoming.resize(max_buffer_size, 0.0);
+        self.right_scratch_incoming.clear();
+        self.right_scratch_incoming.resize(max_buffer_size, 0.0);
+        self.mixed_left.clear();
+        self.mixed_left.resize(max_buffer_size, 0.0);
+        self.mixed_right.clear();
+        self.mixed_right.resize(max_buffer_size, 0.0);
+        self.accum_left.clear();
+        self.accum_left.resize(max_buffer_size, 0.0);
+        self.accum_right.clear();
+        self.accum_right.resize(max_buffer_size, 0.0);
+
+        // Enough capacity for the longest propagation delay at `MAX_DISTANCE_M`,
+        // plus a little headroom for the interpolation read.
+        let delay_capacity =
+            (MAX_DISTANCE_M / SPEED_OF_SOUND_M_S * self.sample_rate).ceil() as usize + 4;
+        self.distance_delay_left = (0..self.num_sources)
+            .map(|_| DistanceDelayLine::new(delay_capacity))
+            .collect();
+        self.distance_delay_right = (0..self.num_sources)
+            .map(|_| DistanceDelayLine::new(delay_capacity))
+            .collect();
+        self.air_absorption_left.clear();
+        self.air_absorption_left.resize(self.num_sources, 0.0);
+        self.air_absorption_right.clear();
+        self.air_absorption_right.resize(self.num_sources, 0.0);
 
         // Resize buffers and perform other potentially expensive initialization operations here.
         // The `reset()` function is always called right after this function. You can remove this
@@ -189,6 +935,24 @@ impl Plugin for HrtfConv {
     fn reset(&mut self) {
         // Reset buffers and envelopes here. This can be called from the audio thread and may not
         // allocate. You can remove this function if you do not need it.
+        for phase in &mut self.orbit_phase {
+            *phase = 0.0;
+        }
+        for phase in &mut self.orbit_elevation_phase {
+            *phase = 0.0;
+        }
+        for line in &mut self.distance_delay_left {
+            line.clear();
+        }
+        for line in &mut self.distance_delay_right {
+            line.clear();
+        }
+        for state in &mut self.air_absorption_left {
+            *state = 0.0;
+        }
+        for state in &mut self.air_absorption_right {
+            *state = 0.0;
+        }
     }
 
     fn process(
@@ -197,31 +961,75 @@ impl Plugin for HrtfConv {
         _aux: &mut AuxiliaryBuffers,
         _context: &mut impl ProcessContext<Self>,
     ) -> ProcessStatus {
-        let render = match &mut self.renderer {
-            Some(r) => r,
-            None => return ProcessStatus::Normal,
-        };
+        if self.renderers.is_empty() {
+            return ProcessStatus::Normal;
+        }
 
-        let az_deg = self.params.azimuth.value();
-        let el_deg = self.params.elevation.value();
-        let dist = self.params.distance.value();
-        let az = az_deg.to_radians();
-        let el = el_deg.to_radians();
-        let x = dist * (el.cos() * az.cos());
-        let y = dist * (el.cos() * az.sin());
-        let z = dist * el.sin();
-        let current_direction = (x, y, z);
-
-        if current_direction != self.last_direction {
-            if let Some(sofa) = self.sofa.as_mut() {
-                if let Some(filter) = self.filter.as_mut() {
-                    sofa.filter(x, y, z, filter);
-                    if let Err(e) = render.set_filter(filter) {
+        self.advance_orbit_phases(buffer.samples());
+        let directions = self.source_directions();
+
+        // Swap in a background SOFA rebuild that finished since the last
+        // block, if any, before any filter updates or rendering this block.
+        if let Some(result) = self.pending_sofa_build.lock().unwrap().take() {
+            self.sofa_reload_in_flight = false;
+            match result {
+                Ok(built) => self.apply_built_sofa(built),
+                Err(()) => nih_error!("Failed to rebuild HRTF renderers for the new SOFA file"),
+            }
+        }
+
+        // Compare under the read guard first so the common case (no SOFA
+        // path change) doesn't pay for a `PathBuf` clone on every block.
+        let path_changed = *self.params.sofa_path.read().unwrap() != self.loaded_sofa_path;
+        if path_changed {
+            // The user picked a new SOFA database: `filter_len` may have
+            // changed, so every renderer has to be rebuilt from scratch
+            // rather than just re-filtered. That involves blocking disk IO
+            // and FFT planning, so it's dispatched to a background task
+            // instead of running inline; the old renderers keep playing
+            // until `task_executor` finishes and the result is swapped in
+            // above.
+            if !self.sofa_reload_in_flight {
+                let current_path = self.params.sofa_path.read().unwrap().clone();
+                self.sofa_reload_in_flight = true;
+                _context.execute_background(SofaReloadTask {
+                    path: current_path,
+                    sample_rate: self.sample_rate,
+                    directions: directions.clone(),
+                    interpolate: self.params.interpolate_hrir.value(),
+                });
+            }
+        } else if let Some(sofa) = self.sofa.as_ref() {
+            let interpolate = self.params.interpolate_hrir.value();
+            let filter_len = sofa.filter_len();
+            for i in 0..self.num_sources {
+                // Don't retarget a source that's already mid-crossfade: let
+                // it settle on its current destination first.
+                if self.crossfade[i].is_none() && directions[i] != self.last_directions[i] {
+                    let incoming_slot = 1 - self.active_slot[i];
+                    Self::compute_filter(
+                        sofa,
+                        filter_len,
+                        directions[i],
+                        interpolate,
+                        &mut self.filters[incoming_slot][i],
+                        &mut self.corner_filter_scratch,
+                        &mut self.corner_shift_left,
+                        &mut self.corner_shift_right,
+                    );
+                    if let Err(e) =
+                        self.renderers[incoming_slot][i].set_filter(&self.filters[incoming_slot][i])
+                    {
                         nih_error!("Failed to set filter:{}", e);
                         return ProcessStatus::Error("HRTF processing failed");
                     }
 
-                    self.last_direction = current_direction;
+                    self.crossfade[i] = Some(CrossfadeState {
+                        incoming_slot,
+                        samples_done: 0,
+                        ramp_len: self.crossfade_len_samples,
+                    });
+                    self.last_directions[i] = directions[i];
                 }
             }
         }
@@ -235,18 +1043,82 @@ impl Plugin for HrtfConv {
         }
 
         // no allocation here
-        self.scratch_buffer.clear();
-        self.scratch_buffer.extend_from_slice(channels[0]);
+        for sample in &mut self.accum_left[..num_samples] {
+            *sample = 0.0;
+        }
+        for sample in &mut self.accum_right[..num_samples] {
+            *sample = 0.0;
+        }
 
-        let (left_chan, right_chan) = channels.split_at_mut(1);
-        let left_out = &mut left_chan[0][..num_samples];
-        let right_out = &mut right_chan[0][..num_samples];
+        for i in 0..self.num_sources {
+            self.scratch_buffer.clear();
+            self.scratch_buffer
+                .extend_from_slice(&channels[i][..num_samples]);
+
+            let active_slot = self.active_slot[i];
+
+            if let Err(e) = self.renderers[active_slot][i].process_block(
+                &self.scratch_buffer,
+                &mut self.left_scratch[..num_samples],
+                &mut self.right_scratch[..num_samples],
+            ) {
+                nih_error!("HRTF render error:{}", e);
+                return ProcessStatus::Error("HRTF processing failed");
+            }
+
+            match &mut self.crossfade[i] {
+                None => {
+                    self.mixed_left[..num_samples]
+                        .copy_from_slice(&self.left_scratch[..num_samples]);
+                    self.mixed_right[..num_samples]
+                        .copy_from_slice(&self.right_scratch[..num_samples]);
+                }
+                Some(state) => {
+                    let incoming_slot = state.incoming_slot;
+                    if let Err(e) = self.renderers[incoming_slot][i].process_block(
+                        &self.scratch_buffer,
+                        &mut self.left_scratch_incoming[..num_samples],
+                        &mut self.right_scratch_incoming[..num_samples],
+                    ) {
+                        nih_error!("HRTF render error:{}", e);
+                        return ProcessStatus::Error("HRTF processing failed");
+                    }
+
+                    for n in 0..num_samples {
+                        let (gain_old, gain_new) = state.gains_at(n);
+
+                        self.mixed_left[n] = gain_old * self.left_scratch[n]
+                            + gain_new * self.left_scratch_incoming[n];
+                        self.mixed_right[n] = gain_old * self.right_scratch[n]
+                            + gain_new * self.right_scratch_incoming[n];
+                    }
+
+                    state.samples_done += num_samples;
+                    if state.samples_done >= state.ramp_len {
+                        self.active_slot[i] = incoming_slot;
+                        self.crossfade[i] = None;
+                    }
+                }
+            }
 
-        if let Err(e) = render.process_block(&self.scratch_buffer, left_out, right_out) {
-            nih_error!("HRTF render error:{}", e);
-            return ProcessStatus::Error("HRTF processing failed");
+            if self.params.sources[i].distance_model.value() {
+                // Use the direction actually driving this block's HRIR (which
+                // substitutes `orbit_radius` for `distance` while orbiting),
+                // not the raw `distance` parameter, so the distance cues stay
+                // consistent with what was rendered.
+                self.apply_distance_model(i, directions[i].2, num_samples);
+            }
+
+            for n in 0..num_samples {
+                self.accum_left[n] += self.mixed_left[n];
+                self.accum_right[n] += self.mixed_right[n];
+            }
         }
 
+        let (left_chan, right_chan) = channels.split_at_mut(1);
+        left_chan[0][..num_samples].copy_from_slice(&self.accum_left[..num_samples]);
+        right_chan[0][..num_samples].copy_from_slice(&self.accum_right[..num_samples]);
+
         ProcessStatus::Normal
     }
 }
@@ -271,3 +1143,116 @@ impl Vst3Plugin for HrtfConv {
 
 nih_export_clap!(HrtfConv);
 nih_export_vst3!(HrtfConv);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crossfade_gains_ramp_from_old_to_new() {
+        let state = CrossfadeState {
+            incoming_slot: 1,
+            samples_done: 0,
+            ramp_len: 4,
+        };
+
+        assert_eq!(state.gains_at(0), (1.0, 0.0));
+        assert_eq!(state.gains_at(2), (0.5, 0.5));
+        assert_eq!(state.gains_at(4), (0.0, 1.0));
+    }
+
+    #[test]
+    fn crossfade_gains_clamp_past_ramp_end() {
+        let state = CrossfadeState {
+            incoming_slot: 1,
+            samples_done: 10,
+            ramp_len: 4,
+        };
+
+        assert_eq!(state.gains_at(0), (0.0, 1.0));
+    }
+
+    #[test]
+    fn onset_index_finds_peak_magnitude_sample() {
+        let ir = [0.0, 0.1, -0.8, 0.3, 0.05];
+        assert_eq!(HrtfConv::onset_index(&ir), 2);
+    }
+
+    #[test]
+    fn onset_index_defaults_to_zero_for_silence() {
+        let ir = [0.0, 0.0, 0.0, 0.0];
+        assert_eq!(HrtfConv::onset_index(&ir), 0);
+    }
+
+    #[test]
+    fn shift_into_moves_samples_right_and_zero_fills() {
+        let ir = [1.0, 2.0, 3.0, 4.0];
+        let mut dst = [0.0; 4];
+        HrtfConv::shift_into(&ir, 1, &mut dst);
+        assert_eq!(dst, [0.0, 1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn shift_into_moves_samples_left_and_zero_fills() {
+        let ir = [1.0, 2.0, 3.0, 4.0];
+        let mut dst = [0.0; 4];
+        HrtfConv::shift_into(&ir, -1, &mut dst);
+        assert_eq!(dst, [2.0, 3.0, 4.0, 0.0]);
+    }
+
+    #[test]
+    fn shift_into_zero_is_identity() {
+        let ir = [1.0, 2.0, 3.0, 4.0];
+        let mut dst = [0.0; 4];
+        HrtfConv::shift_into(&ir, 0, &mut dst);
+        assert_eq!(dst, ir);
+    }
+
+    #[test]
+    fn bilinear_corners_on_grid_point_has_unit_weight() {
+        let corners = HrtfConv::bilinear_corners(10.0, 20.0);
+        let on_point = corners
+            .iter()
+            .find(|&&(az, el, _)| az == 10.0 && el == 20.0)
+            .expect("grid point should be one of the four corners");
+        assert_eq!(on_point.2, 1.0);
+        for &(az, el, weight) in &corners {
+            if (az, el) != (10.0, 20.0) {
+                assert_eq!(weight, 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn bilinear_corners_at_midpoint_splits_weight_evenly() {
+        let half = INTERP_GRID_DEG / 2.0;
+        let corners = HrtfConv::bilinear_corners(half, half);
+        for &(_, _, weight) in &corners {
+            assert!((weight - 0.25).abs() < 1e-6);
+        }
+        let weight_sum: f32 = corners.iter().map(|&(_, _, w)| w).sum();
+        assert!((weight_sum - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn distance_delay_line_integer_delay_passes_through() {
+        let mut line = DistanceDelayLine::new(8);
+        let input = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let mut output = vec![];
+        for &sample in &input {
+            output.push(line.process(sample, 2.0));
+        }
+        assert_eq!(output, vec![0.0, 0.0, 1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn distance_delay_line_fractional_delay_interpolates() {
+        let mut line = DistanceDelayLine::new(8);
+        line.process(1.0, 1.5);
+        line.process(2.0, 1.5);
+        let out = line.process(3.0, 1.5);
+        // A 1.5-sample delay reads halfway between the previous two writes
+        // (`2.0` and `1.0`), so the output is their average.
+        assert!((out - 1.5).abs() < 1e-6);
+    }
+}